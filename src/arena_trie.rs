@@ -1,31 +1,39 @@
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
 use std::sync::{Arc, RwLock};
 
 type ArenaTrieIndex = usize;
-type ArenaTrieNodeValue = char; // TODO: generic over type T
 
 #[derive(Clone, Eq, PartialEq)]
-enum ArenaTrieNodeType {
-    Final(String),
+enum ArenaTrieNodeType<K> {
+    Final(Vec<K>),
     Intermediate,
 }
 
-#[derive(Clone, Eq, PartialEq)]
-struct ArenaTrieNode {
-    children: HashMap<ArenaTrieNodeValue, ArenaTrieIndex>,
-    node_type: ArenaTrieNodeType,
+#[derive(Clone)]
+struct ArenaTrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    children: HashMap<K, ArenaTrieIndex>,
+    node_type: ArenaTrieNodeType<K>,
     word_score: Option<i64>,
     aggregate_score: i64,
+    payload: Option<V>,
 }
 
-impl ArenaTrieNode {
+impl<K, V> ArenaTrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn new() -> Self {
         ArenaTrieNode {
             children: HashMap::new(),
             node_type: ArenaTrieNodeType::Intermediate,
             word_score: None,
             aggregate_score: 0,
+            payload: None,
         }
     }
 
@@ -40,59 +48,88 @@ impl ArenaTrieNode {
     }
 }
 
-impl Ord for ArenaTrieNode {
+// `Eq`/`Ord` are implemented in terms of the ranking score alone, so the payload type `V` never
+// needs to be comparable.
+impl<K, V> PartialEq for ArenaTrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.get_ranking_score() == other.get_ranking_score()
+    }
+}
+
+impl<K, V> Eq for ArenaTrieNode<K, V> where K: Eq + Hash + Clone {}
+
+impl<K, V> Ord for ArenaTrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn cmp(&self, other: &Self) -> Ordering {
         self.get_ranking_score().cmp(&other.get_ranking_score())
     }
 }
 
-impl PartialOrd for ArenaTrieNode {
+impl<K, V> PartialOrd for ArenaTrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-pub struct ArenaTrie {
-    arena: Arc<RwLock<Vec<ArenaTrieNode>>>,
+pub struct ArenaTrie<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    arena: Arc<RwLock<Vec<ArenaTrieNode<K, V>>>>,
 }
 
-impl ArenaTrie {
+impl<K, V> ArenaTrie<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
     pub fn new() -> Self {
         ArenaTrie {
             arena: Arc::new(RwLock::new(vec![ArenaTrieNode::new()])),
         }
     }
 
-    fn _insert(&mut self, word: String, score: i64) {
+    fn _insert<I: IntoIterator<Item = K>>(&mut self, seq: I, score: i64, payload: Option<V>) {
         let mut arena = self.arena.write().expect("RwLock poisoned");
 
         let mut current_node_index = 0;
+        let mut symbols: Vec<K> = vec![];
 
-        for char in word.chars() {
-            if let Some(next_idx) = arena[current_node_index].children.get(&char) {
+        for symbol in seq {
+            if let Some(next_idx) = arena[current_node_index].children.get(&symbol) {
                 current_node_index = *next_idx;
             } else {
                 // TODO: does the ordering of operations matter here?
                 let next_idx = arena.len();
                 arena.push(ArenaTrieNode::new());
                 let node_to_mod = &mut arena[current_node_index];
-                node_to_mod.children.insert(char, next_idx);
+                node_to_mod.children.insert(symbol.clone(), next_idx);
                 current_node_index = next_idx;
             }
+            symbols.push(symbol);
         }
 
-        let mut current_node = &mut arena[current_node_index];
-        current_node.node_type = ArenaTrieNodeType::Final(word);
+        let current_node = &mut arena[current_node_index];
+        current_node.node_type = ArenaTrieNodeType::Final(symbols);
         current_node.word_score = Some(score);
+        current_node.payload = payload;
     }
 
-    fn _search(&self, word: &String) -> Option<ArenaTrieIndex> {
+    fn _search<I: IntoIterator<Item = K>>(&self, seq: I) -> Option<ArenaTrieIndex> {
         let arena = self.arena.read().expect("RwLock poisoned");
 
         let mut current_node_index = 0;
 
-        for char in word.chars() {
-            match arena[current_node_index].children.get(&char) {
+        for symbol in seq {
+            match arena[current_node_index].children.get(&symbol) {
                 Some(next_idx) => current_node_index = *next_idx,
                 None => return None,
             }
@@ -101,20 +138,23 @@ impl ArenaTrie {
         Some(current_node_index)
     }
 
-    pub fn get_ranked_results(&self, prefix: String) -> Option<Vec<String>> {
+    pub fn get_ranked_results<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+    ) -> Option<Vec<(Vec<K>, Option<V>)>> {
         let arena = self.arena.read().expect("RwLock poisoned");
 
-        let initial_children = match self._search(&prefix) {
+        let initial_children = match self._search(prefix) {
             Some(idx) => &arena[idx].children,
             _ => return None,
         };
 
-        // Our collection of "found" items is represented by `TrieNode` instances themselves so
-        // that we can order by the underlying word's score before returning.
-        let mut found_nodes: BinaryHeap<&ArenaTrieNode> = BinaryHeap::new();
+        // Our collection of "found" items is represented by `ArenaTrieNode` instances themselves so
+        // that we can order by the underlying entry's score before returning.
+        let mut found_nodes: BinaryHeap<&ArenaTrieNode<K, V>> = BinaryHeap::new();
 
         // TODO: Can we switch this to a `VecDeque` for any kind of savings?
-        let mut heap: BinaryHeap<&ArenaTrieNode> =
+        let mut heap: BinaryHeap<&ArenaTrieNode<K, V>> =
             initial_children.values().map(|idx| &arena[*idx]).collect();
 
         while let Some(next_node) = heap.pop() {
@@ -129,12 +169,14 @@ impl ArenaTrie {
         // NOTE: It's a bit convoluted to turn a `BinaryHeap` into a `Vec` with the values in heap
         // order. `BinaryHeap.into_iter_sorted` will do what we need, but it is not yet stable (see
         // https://github.com/rust-lang/rust/issues/59278).
-        let result: Vec<String> = found_nodes
+        let result: Vec<(Vec<K>, Option<V>)> = found_nodes
             .into_sorted_vec()
             .iter()
             .rev()
             .filter_map(|node| match &node.node_type {
-                ArenaTrieNodeType::Final(word) => Some(word.to_string()),
+                ArenaTrieNodeType::Final(symbols) => {
+                    Some((symbols.to_owned(), node.payload.clone()))
+                }
                 _ => None,
             })
             .collect();
@@ -142,62 +184,164 @@ impl ArenaTrie {
         Some(result)
     }
 
-    pub fn insert(&mut self, word: String) {
-        self._insert(word, 0);
+    pub fn insert<I: IntoIterator<Item = K>>(&mut self, seq: I) {
+        self._insert(seq, 0, None);
     }
 
-    pub fn insert_with_score(&mut self, word: String, score: i64) {
-        self._insert(word, score);
+    pub fn insert_with_score<I: IntoIterator<Item = K>>(&mut self, seq: I, score: i64) {
+        self._insert(seq, score, None);
     }
 
-    pub fn search(&self, word: String) -> Option<String> {
+    pub fn insert_with_value<I: IntoIterator<Item = K>>(&mut self, seq: I, score: i64, value: V) {
+        self._insert(seq, score, Some(value));
+    }
+
+    pub fn search<I: IntoIterator<Item = K>>(&self, seq: I) -> Option<(Vec<K>, Option<V>)> {
         let arena = self.arena.read().expect("RwLock poisoned");
 
-        match self._search(&word) {
+        match self._search(seq) {
             Some(idx) => match &arena[idx] {
                 ArenaTrieNode {
-                    node_type: ArenaTrieNodeType::Final(result),
+                    node_type: ArenaTrieNodeType::Final(symbols),
+                    payload,
                     ..
-                } => Some(result.to_string()),
-                _ => return None,
+                } => Some((symbols.to_owned(), payload.clone())),
+                _ => None,
             },
             _ => None,
         }
     }
 
-    pub fn starts_with(&self, prefix: String) -> Option<String> {
-        match self._search(&prefix) {
-            Some(_) => Some(prefix),
+    pub fn starts_with<I: IntoIterator<Item = K> + Clone>(&self, prefix: I) -> Option<Vec<K>> {
+        match self._search(prefix.clone()) {
+            Some(_) => Some(prefix.into_iter().collect()),
             _ => None,
         }
     }
 }
 
+impl<K, V> Default for ArenaTrie<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Thin specialization of `ArenaTrie<K, V>` for `char`-keyed words, matching the fuzzy-search
+/// API `Trie<char, V>` offers.
+impl<V> ArenaTrie<char, V>
+where
+    V: Clone,
+{
+    /// Typo-tolerant prefix search: returns every stored word whose prefix is within
+    /// `max_edits` edits of `query`, ranked using the same scoring as `get_ranked_results`. This
+    /// gives autocomplete that tolerates user typos, e.g. `query` `"Forex"` with `max_edits: 1`
+    /// matches a longer stored word like `"Foreign"`, not just same-length near-matches.
+    pub fn get_fuzzy_results(&self, query: String, max_edits: u8) -> Vec<String> {
+        let query_chars: Vec<char> = query.chars().collect();
+        let initial_row: Vec<usize> = (0..=query_chars.len()).collect();
+
+        let arena = self.arena.read().expect("RwLock poisoned");
+        let mut found_nodes: BinaryHeap<&ArenaTrieNode<char, V>> = BinaryHeap::new();
+        Self::collect_fuzzy_matches(
+            &arena,
+            0,
+            &query_chars,
+            &initial_row,
+            max_edits,
+            false,
+            &mut found_nodes,
+        );
+
+        found_nodes
+            .into_sorted_vec()
+            .iter()
+            .rev()
+            .filter_map(|node| match &node.node_type {
+                ArenaTrieNodeType::Final(symbols) => Some(symbols.iter().collect()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Mirrors `Trie::collect_fuzzy_matches` — see there for the full "prefix mode" rationale:
+    // `row.last()` at a given depth is `edit_distance(query, path_to_this_node)`, and once that's
+    // `<= max_edits` at some depth, every `Final` node further down the same branch is still a
+    // match, so `prefix_matched` carries that state down the recursion and short-circuits the DP
+    // work once set.
+    fn collect_fuzzy_matches<'a>(
+        arena: &'a [ArenaTrieNode<char, V>],
+        node_idx: ArenaTrieIndex,
+        query: &[char],
+        row: &[usize],
+        max_edits: u8,
+        prefix_matched: bool,
+        found: &mut BinaryHeap<&'a ArenaTrieNode<char, V>>,
+    ) {
+        let node = &arena[node_idx];
+        let prefix_matched = prefix_matched || *row.last().unwrap() <= max_edits as usize;
+
+        if prefix_matched {
+            if let ArenaTrieNodeType::Final(_) = node.node_type {
+                found.push(node);
+            }
+
+            for &child_idx in node.children.values() {
+                Self::collect_fuzzy_matches(arena, child_idx, query, row, max_edits, true, found);
+            }
+            return;
+        }
+
+        for (symbol, &child_idx) in node.children.iter() {
+            let mut child_row = vec![row[0] + 1];
+            for i in 1..=query.len() {
+                let substitution_cost = usize::from(query[i - 1] != *symbol);
+                child_row.push(
+                    (child_row[i - 1] + 1) // insertion
+                        .min(row[i] + 1) // deletion
+                        .min(row[i - 1] + substitution_cost), // substitution
+                );
+            }
+
+            if *child_row.iter().min().unwrap() > max_edits as usize {
+                continue;
+            }
+
+            Self::collect_fuzzy_matches(
+                arena, child_idx, query, &child_row, max_edits, false, found,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::ArenaTrie;
+    use crate::arena_trie::ArenaTrie;
 
     #[test]
     fn can_search_for_term() {
         let search_term = "Foo";
-        let mut trie = ArenaTrie::new();
-        trie.insert(search_term.to_string());
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert(search_term.chars());
 
         assert_eq!(
-            Some(search_term.to_string()),
-            trie.search(search_term.to_string())
+            Some((search_term.chars().collect(), None)),
+            trie.search(search_term.chars())
         );
     }
 
     #[test]
     fn can_search_for_term_with_score() {
         let search_term = "Foo";
-        let mut trie = ArenaTrie::new();
-        trie.insert_with_score(search_term.to_string(), 10);
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert_with_score(search_term.chars(), 10);
 
         assert_eq!(
-            Some(search_term.to_string()),
-            trie.search(search_term.to_string())
+            Some((search_term.chars().collect(), None)),
+            trie.search(search_term.chars())
         );
     }
 
@@ -206,28 +350,28 @@ mod tests {
         let search_term = "Foo";
         let insert_terms = vec!["Foo", "For"];
 
-        let mut trie = ArenaTrie::new();
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
         for term in insert_terms {
-            trie.insert(term.to_string());
+            trie.insert(term.chars());
         }
 
         assert_eq!(
-            Some(search_term.to_string()),
-            trie.search(search_term.to_string())
+            Some((search_term.chars().collect(), None)),
+            trie.search(search_term.chars())
         );
     }
 
     #[test]
     fn can_find_starts_with_items() {
         let insert_term = "Foo";
-        let mut trie = ArenaTrie::new();
-        trie.insert(insert_term.to_string());
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert(insert_term.chars());
 
         let prefix = "Fo";
 
         assert_eq!(
-            Some(prefix.to_string()),
-            trie.starts_with(prefix.to_string())
+            Some(prefix.chars().collect::<Vec<char>>()),
+            trie.starts_with(prefix.chars())
         );
     }
 
@@ -236,10 +380,10 @@ mod tests {
         let insert_term = "Foo";
         let search_term = "Bar";
 
-        let mut trie = ArenaTrie::new();
-        trie.insert(insert_term.to_string());
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert(insert_term.chars());
 
-        assert_eq!(None, trie.search(search_term.to_string()));
+        assert_eq!(None, trie.search(search_term.chars()));
     }
 
     #[test]
@@ -247,29 +391,103 @@ mod tests {
         let insert_term = "Foo";
         let prefix = "Ba";
 
-        let mut trie = ArenaTrie::new();
-        trie.insert(insert_term.to_string());
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert(insert_term.chars());
 
-        assert_eq!(None, trie.starts_with(prefix.to_string()));
+        assert_eq!(None, trie.starts_with(prefix.chars()));
     }
 
     #[test]
     fn get_ranked_results_uses_score_ordering() {
-        let words_and_scores = vec![("Foreign", 10), ("For", 8), ("Foo", 0)];
+        let words_and_scores = [("Foreign", 10), ("For", 8), ("Foo", 0)];
 
         let expected_words: Vec<String> = words_and_scores
             .iter()
             .map(|(word, _)| word.to_string())
             .collect();
 
-        let mut trie = ArenaTrie::new();
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
 
         for (word, score) in words_and_scores.iter() {
-            trie.insert_with_score(word.to_string(), *score);
+            trie.insert_with_score(word.chars(), *score);
         }
 
-        let ranked_results = trie.get_ranked_results("Fo".to_string()).unwrap();
+        let ranked_results = trie.get_ranked_results("Fo".chars()).unwrap();
+        let ranked_words: Vec<String> = ranked_results
+            .into_iter()
+            .map(|(chars, _)| chars.into_iter().collect())
+            .collect();
+
+        assert_eq!(expected_words, ranked_words);
+    }
+
+    #[test]
+    fn works_over_non_char_symbol_types() {
+        let mut trie: ArenaTrie<u8, ()> = ArenaTrie::new();
+        trie.insert("abc".bytes());
+
+        assert_eq!(
+            Some(("abc".bytes().collect(), None)),
+            trie.search("abc".bytes())
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_finds_exact_match_with_zero_edits() {
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert("Foo".chars());
+
+        assert_eq!(
+            vec!["Foo".to_string()],
+            trie.get_fuzzy_results("Foo".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_tolerates_a_single_substitution() {
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert("Foo".chars());
+
+        assert_eq!(
+            vec!["Foo".to_string()],
+            trie.get_fuzzy_results("Fon".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_excludes_matches_past_max_edits() {
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert("Foo".chars());
+
+        let results = trie.get_fuzzy_results("Fon".to_string(), 0);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn get_fuzzy_results_matches_words_longer_than_the_query() {
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert("Foreign".chars());
+        trie.insert("For".chars());
+        trie.insert("Foo".chars());
 
-        assert_eq!(expected_words, ranked_results);
+        let mut results = trie.get_fuzzy_results("Fo".to_string(), 0);
+        results.sort();
+
+        assert_eq!(
+            vec!["Foo".to_string(), "For".to_string(), "Foreign".to_string()],
+            results
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_ranks_by_score() {
+        let mut trie: ArenaTrie<char, ()> = ArenaTrie::new();
+        trie.insert_with_score("Foo".chars(), 0);
+        trie.insert_with_score("Fon".chars(), 10);
+
+        assert_eq!(
+            vec!["Fon".to_string(), "Foo".to_string()],
+            trie.get_fuzzy_results("Fon".to_string(), 1)
+        );
     }
 }
@@ -0,0 +1,152 @@
+/// A single ranked result under consideration. Built-in `Criterion`s compare on this instead of
+/// each re-deriving the word and its score from the trie's internal node representation.
+pub struct Candidate<'a, K> {
+    pub word: &'a [K],
+    pub word_score: i64,
+}
+
+/// A single ranking rule. `Trie::get_ranked_results_by` applies a list of `Criterion`s
+/// lexicographically: the first criterion dominates, and later ones only break ties left by
+/// earlier ones. Higher `rank` values sort first.
+pub trait Criterion<K> {
+    fn rank(&self, candidate: &Candidate<K>) -> i64;
+}
+
+/// Ranks candidates that equal the query exactly ahead of longer completions.
+pub struct ExactPrefixFirst<K> {
+    prefix: Vec<K>,
+}
+
+impl<K> ExactPrefixFirst<K> {
+    pub fn new(prefix: Vec<K>) -> Self {
+        ExactPrefixFirst { prefix }
+    }
+}
+
+impl<K> Criterion<K> for ExactPrefixFirst<K>
+where
+    K: PartialEq,
+{
+    fn rank(&self, candidate: &Candidate<K>) -> i64 {
+        i64::from(candidate.word == self.prefix.as_slice())
+    }
+}
+
+/// Ranks shorter completions ahead of longer ones.
+pub struct ShorterWordFirst;
+
+impl<K> Criterion<K> for ShorterWordFirst {
+    fn rank(&self, candidate: &Candidate<K>) -> i64 {
+        -(candidate.word.len() as i64)
+    }
+}
+
+/// Ranks candidates closer (by Levenshtein distance) to `query` ahead of more distant ones.
+pub struct EditDistance<K> {
+    query: Vec<K>,
+}
+
+impl<K> EditDistance<K> {
+    pub fn new(query: Vec<K>) -> Self {
+        EditDistance { query }
+    }
+}
+
+impl<K> Criterion<K> for EditDistance<K>
+where
+    K: PartialEq,
+{
+    fn rank(&self, candidate: &Candidate<K>) -> i64 {
+        -(levenshtein_distance(&self.query, candidate.word) as i64)
+    }
+}
+
+fn levenshtein_distance<K: PartialEq>(a: &[K], b: &[K]) -> usize {
+    let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_symbol) in a.iter().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b_symbol) in b.iter().enumerate() {
+            let substitution_cost = usize::from(a_symbol != b_symbol);
+            row.push(
+                (row[j] + 1) // insertion
+                    .min(prev_row[j + 1] + 1) // deletion
+                    .min(prev_row[j] + substitution_cost), // substitution
+            );
+        }
+        prev_row = row;
+    }
+
+    prev_row[b.len()]
+}
+
+/// Ranks candidates by the score they were inserted with — the trie's original, single-criterion
+/// ranking behavior.
+pub struct WordScore;
+
+impl<K> Criterion<K> for WordScore {
+    fn rank(&self, candidate: &Candidate<K>) -> i64 {
+        candidate.word_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_score_ranks_by_insertion_score() {
+        let criterion = WordScore;
+        let candidate = Candidate {
+            word: &['F', 'o', 'o'],
+            word_score: 7,
+        };
+
+        assert_eq!(7, criterion.rank(&candidate));
+    }
+
+    #[test]
+    fn shorter_word_first_prefers_shorter_words() {
+        let criterion = ShorterWordFirst;
+        let short = Candidate {
+            word: &['F', 'o'],
+            word_score: 0,
+        };
+        let long = Candidate {
+            word: &['F', 'o', 'o'],
+            word_score: 0,
+        };
+
+        assert!(criterion.rank(&short) > criterion.rank(&long));
+    }
+
+    #[test]
+    fn exact_prefix_first_prefers_an_exact_match() {
+        let criterion = ExactPrefixFirst::new(vec!['F', 'o']);
+        let exact = Candidate {
+            word: &['F', 'o'],
+            word_score: 0,
+        };
+        let longer = Candidate {
+            word: &['F', 'o', 'o'],
+            word_score: 0,
+        };
+
+        assert!(criterion.rank(&exact) > criterion.rank(&longer));
+    }
+
+    #[test]
+    fn edit_distance_prefers_closer_matches() {
+        let criterion = EditDistance::new(vec!['F', 'o', 'n']);
+        let close = Candidate {
+            word: &['F', 'o', 'o'],
+            word_score: 0,
+        };
+        let far = Candidate {
+            word: &['B', 'a', 'r'],
+            word_score: 0,
+        };
+
+        assert!(criterion.rank(&close) > criterion.rank(&far));
+    }
+}
@@ -1,49 +1,63 @@
 use std::cmp::Ordering;
+use std::hash::Hash;
 
-use crate::helpers::queue_wrapper::QueueWrapper;
-use crate::trie_node::TrieNode;
+use crate::trie_node::{TrieNode, TrieNodeType};
 
-#[derive(Clone, Eq, PartialEq)]
-pub struct OutputWrapper<'a> {
-    pub nodes: Vec<&'a TrieNode>,
+#[derive(Clone)]
+pub struct OutputWrapper<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    pub node: &'a TrieNode<K, V>,
 }
 
-impl<'a> OutputWrapper<'a> {
-    pub fn join(&self) -> String {
-        self.nodes
-            .iter()
-            .map(|n| n.value.unwrap_or_default())
-            .collect::<String>()
+impl<'a, K, V> OutputWrapper<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn join(&self) -> Vec<K> {
+        self.node.word.clone().unwrap_or_default()
     }
 
-    pub fn last(&self) -> Option<&&'a TrieNode> {
-        self.nodes.last()
+    pub fn payload(&self) -> Option<&'a V> {
+        self.node.payload.as_ref()
+    }
+
+    pub fn leaf_type(&self) -> TrieNodeType {
+        self.node.node_type
     }
 
     pub fn output_score(&self) -> i64 {
-        match self.last() {
-            Some(node) => match node.word_score {
-                Some(score) => score,
-                _ => 0,
-            },
-            _ => 0,
-        }
+        self.node.word_score.unwrap_or_default()
     }
+}
 
-    pub fn to_queue_wrapper(&self) -> QueueWrapper<'a> {
-        QueueWrapper {
-            nodes: self.nodes.to_owned(),
-        }
+// `Eq`/`Ord` are implemented in terms of `output_score` alone, so the payload type `V` never needs
+// to be comparable.
+impl<K, V> PartialEq for OutputWrapper<'_, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.output_score() == other.output_score()
     }
 }
 
-impl Ord for OutputWrapper<'_> {
+impl<K, V> Eq for OutputWrapper<'_, K, V> where K: Eq + Hash + Clone {}
+
+impl<K, V> Ord for OutputWrapper<'_, K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn cmp(&self, other: &Self) -> Ordering {
         self.output_score().cmp(&other.output_score())
     }
 }
 
-impl PartialOrd for OutputWrapper<'_> {
+impl<K, V> PartialOrd for OutputWrapper<'_, K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -1,60 +1,73 @@
 use std::cmp::Ordering;
-use std::collections::hash_map::Values;
+use std::hash::Hash;
 
 use crate::helpers::output_wrapper::OutputWrapper;
 use crate::trie_node::{TrieNode, TrieNodeType};
 
-#[derive(Clone, Eq, PartialEq)]
-pub struct QueueWrapper<'a> {
-    pub nodes: Vec<&'a TrieNode>,
+#[derive(Clone)]
+pub struct QueueWrapper<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    pub node: &'a TrieNode<K, V>,
+    pub arena: &'a [TrieNode<K, V>],
 }
 
-impl<'a> QueueWrapper<'a> {
-    pub fn last(&self) -> Option<&&'a TrieNode> {
-        self.nodes.last()
+impl<'a, K, V> QueueWrapper<'a, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn output_score(&self) -> i64 {
+        self.node.node_score
     }
 
-    pub fn output_score(&self) -> i64 {
-        match self.last() {
-            Some(node) => node.node_score,
-            _ => 0,
-        }
+    pub fn to_output_wrapper(&self) -> OutputWrapper<'a, K, V> {
+        OutputWrapper { node: self.node }
     }
 
-    pub fn to_output_wrapper(&self) -> OutputWrapper<'a> {
-        OutputWrapper {
-            nodes: self.nodes.to_owned(),
+    pub fn new_with_node(&self, node: &'a TrieNode<K, V>) -> Self {
+        Self {
+            node,
+            arena: self.arena,
         }
     }
 
-    pub fn new_with_node(&self, node: &'a TrieNode) -> Self {
-        let mut nodes = self.nodes.to_owned();
-        nodes.push(node);
-        Self { nodes }
+    pub fn children(&self) -> impl Iterator<Item = &'a TrieNode<K, V>> + '_ {
+        self.node
+            .children
+            .values()
+            .map(move |&idx| &self.arena[idx])
     }
 
-    pub fn children(&self) -> Option<Values<'a, char, TrieNode>> {
-        match self.last() {
-            Some(node) => Some(node.children.values()),
-            _ => None,
-        }
+    pub fn leaf_type(&self) -> TrieNodeType {
+        self.node.node_type
     }
+}
 
-    pub fn leaf_type(&self) -> Option<TrieNodeType> {
-        match self.last() {
-            Some(node) => Some(node.node_type),
-            _ => None,
-        }
+impl<K, V> PartialEq for QueueWrapper<'_, K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.output_score() == other.output_score()
     }
 }
 
-impl Ord for QueueWrapper<'_> {
+impl<K, V> Eq for QueueWrapper<'_, K, V> where K: Eq + Hash + Clone {}
+
+impl<K, V> Ord for QueueWrapper<'_, K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn cmp(&self, other: &Self) -> Ordering {
         self.output_score().cmp(&other.output_score())
     }
 }
 
-impl PartialOrd for QueueWrapper<'_> {
+impl<K, V> PartialOrd for QueueWrapper<'_, K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+
+use crate::trie::Trie;
+use crate::trie_node::{TrieNode, TrieNodeType};
+
+/// An online, Aho-Corasick-style matcher over a stream of `char`s, built once from a
+/// `Trie<char, V>`. Each call to `query` advances one character further into the stream —
+/// falling back through precomputed failure links whenever there is no matching child — and
+/// reports whether the characters seen so far end with any word the source trie contains,
+/// without re-scanning history.
+///
+/// The arena is snapshotted at construction time; inserts made into the source trie afterwards
+/// are not reflected here.
+pub struct StreamMatcher<V> {
+    arena: Vec<TrieNode<char, V>>,
+    fail_links: Vec<usize>,
+    current: usize,
+}
+
+impl<V> StreamMatcher<V>
+where
+    V: Clone,
+{
+    pub fn new(trie: &Trie<char, V>) -> Self {
+        let arena = trie.arena_snapshot();
+        let fail_links = Self::build_fail_links(&arena);
+
+        StreamMatcher {
+            arena,
+            fail_links,
+            current: 0,
+        }
+    }
+
+    // BFS over the trie computing each node's failure link: the node matching the longest
+    // proper suffix of the current path that is also a prefix in the trie. The root's children
+    // link straight back to the root, and every other node reached by symbol `c` from parent `p`
+    // links to `goto(fail(p), c)`.
+    fn build_fail_links(arena: &[TrieNode<char, V>]) -> Vec<usize> {
+        let mut fail_links = vec![0; arena.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+
+        for &child_idx in arena[0].children.values() {
+            queue.push_back(child_idx);
+        }
+
+        while let Some(idx) = queue.pop_front() {
+            for (symbol, &child_idx) in arena[idx].children.iter() {
+                let mut fallback = fail_links[idx];
+                while fallback != 0 && !arena[fallback].children.contains_key(symbol) {
+                    fallback = fail_links[fallback];
+                }
+                fail_links[child_idx] = arena[fallback].children.get(symbol).copied().unwrap_or(0);
+                queue.push_back(child_idx);
+            }
+        }
+
+        fail_links
+    }
+
+    /// Feeds a single character into the stream, returning `true` if the characters received so
+    /// far (across every call to `query`) end with a word stored in the source trie.
+    pub fn query(&mut self, c: char) -> bool {
+        while self.current != 0 && !self.arena[self.current].children.contains_key(&c) {
+            self.current = self.fail_links[self.current];
+        }
+
+        self.current = self.arena[self.current]
+            .children
+            .get(&c)
+            .copied()
+            .unwrap_or(0);
+
+        self.matches_here()
+    }
+
+    // A match is reported if the node we've landed on, or any node reachable by following its
+    // failure chain back toward the root, is `Final` — i.e. some suffix of the characters seen so
+    // far is a stored word.
+    fn matches_here(&self) -> bool {
+        let mut idx = self.current;
+        loop {
+            if self.arena[idx].node_type == TrieNodeType::Final {
+                return true;
+            }
+            if idx == 0 {
+                return false;
+            }
+            idx = self.fail_links[idx];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trie::Trie;
+
+    #[test]
+    fn query_reports_a_match_at_the_end_of_a_stored_word() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("he".to_string());
+
+        let mut matcher = trie.stream_matcher();
+
+        assert!(!matcher.query('t'));
+        assert!(!matcher.query('h'));
+        assert!(matcher.query('e'));
+    }
+
+    #[test]
+    fn query_matches_via_a_failure_link_after_a_partial_match() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("shell".to_string());
+        trie.insert_word("he".to_string());
+
+        let mut matcher = trie.stream_matcher();
+
+        assert!(!matcher.query('s'));
+        assert!(!matcher.query('h'));
+        assert!(matcher.query('e'));
+    }
+
+    #[test]
+    fn query_matches_multiple_words_across_a_single_stream() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("he".to_string());
+        trie.insert_word("she".to_string());
+        trie.insert_word("his".to_string());
+
+        let mut matcher = trie.stream_matcher();
+        let matched: Vec<bool> = "ushers".chars().map(|c| matcher.query(c)).collect();
+
+        assert_eq!(vec![false, false, false, true, false, false], matched);
+    }
+
+    #[test]
+    fn query_returns_false_when_nothing_matches() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("cat".to_string());
+
+        let mut matcher = trie.stream_matcher();
+        let matched: Vec<bool> = "dog".chars().map(|c| matcher.query(c)).collect();
+
+        assert_eq!(vec![false, false, false], matched);
+    }
+}
@@ -1,185 +1,554 @@
 use std::cmp;
 use std::collections::BinaryHeap;
+use std::hash::Hash;
 use std::sync::{Arc, RwLock};
 
+use crate::criteria::{Candidate, Criterion};
 use crate::helpers::output_wrapper::OutputWrapper;
 use crate::helpers::queue_wrapper::QueueWrapper;
+use crate::stream_matcher::StreamMatcher;
 use crate::trie_node::{TrieNode, TrieNodeType};
 
+/// A trie keyed on a sequence of `K` symbols (e.g. `char` for words, `u8` for
+/// byte strings, `u32` for tokenized sequences), with an optional `V` payload
+/// attached to each stored (`Final`) entry.
+///
+/// Nodes live in a single flat arena (`Vec<TrieNode<K, V>>`) addressed by index, rather than as
+/// a tree of owned nodes, so that descending an edge is an index lookup instead of a pointer
+/// chase through per-node locks.
 #[derive(Clone)]
-pub struct Trie {
-    root: TrieNode,
-    root_index: usize,
-    arena: Arc<RwLock<Vec<Arc<RwLock<TrieNode>>>>>,
+pub struct Trie<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    arena: Arc<RwLock<Vec<TrieNode<K, V>>>>,
 }
 
-impl Trie {
+impl<K, V> Trie<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
     pub fn new() -> Self {
         Trie {
-            root: TrieNode::new(None),
-            root_index: 0,
-            arena: Arc::new(RwLock::new(vec![Arc::new(RwLock::new(TrieNode::new(
-                None,
-            )))])),
+            arena: Arc::new(RwLock::new(vec![TrieNode::new()])),
         }
     }
 
-    fn _insert(&mut self, word: String, score: i64) {
-        let mut current_node = &mut self.root;
+    // Walks `seq` from the root, returning the arena index of the node it lands on, if any.
+    // Shared by every read path so that callers already holding the arena read lock (e.g.
+    // `_get_ranked_results`) don't have to re-acquire it through `_search`.
+    fn locate<I: IntoIterator<Item = K>>(arena: &[TrieNode<K, V>], seq: I) -> Option<usize> {
+        let mut current_node_index = 0;
 
-        let mut arena = self.arena.write().expect("RwLock poisoned");
-
-        let mut current_node_index: usize = 0;
+        for symbol in seq {
+            match arena[current_node_index].children.get(&symbol) {
+                Some(&next_idx) => current_node_index = next_idx,
+                None => return None,
+            }
+        }
 
-        for char in word.chars() {
-            let mut next_node = current_node
-                .children
-                .entry(char)
-                .or_insert(TrieNode::new(Some(char)));
-            next_node.node_score = cmp::max(next_node.node_score, score);
-            current_node = next_node;
+        Some(current_node_index)
+    }
 
-            if let Some(next_idx) = arena
-                .get(current_node_index)
-                .unwrap()
-                .read()
-                .ok()
-                .unwrap()
-                .children_new
-                .get(&char)
-            {
-                current_node_index = *next_idx;
-                continue;
-            }
-            let next_idx = arena.len();
-            arena.push(Arc::new(RwLock::new(TrieNode::new(Some(char)))));
+    // Clones the current arena out from behind its lock. Used by auxiliary structures (e.g.
+    // `StreamMatcher`'s failure links) that need to walk the whole trie once, up front, rather
+    // than per read path.
+    pub(crate) fn arena_snapshot(&self) -> Vec<TrieNode<K, V>> {
+        self.arena.read().expect("RwLock poisoned").clone()
+    }
 
-            let mut node_to_mod = arena[current_node_index].write().unwrap();
-            node_to_mod.children_new.insert(char, next_idx);
+    fn _insert<I: IntoIterator<Item = K>>(&mut self, seq: I, score: i64, payload: Option<V>) {
+        let mut arena = self.arena.write().expect("RwLock poisoned");
 
+        let mut current_node_index = 0;
+        let mut symbols: Vec<K> = vec![];
+
+        for symbol in seq {
+            let next_idx = match arena[current_node_index].children.get(&symbol) {
+                Some(&next_idx) => next_idx,
+                None => {
+                    let next_idx = arena.len();
+                    arena.push(TrieNode::new());
+                    arena[current_node_index]
+                        .children
+                        .insert(symbol.clone(), next_idx);
+                    next_idx
+                }
+            };
+            arena[next_idx].node_score = cmp::max(arena[next_idx].node_score, score);
             current_node_index = next_idx;
+            symbols.push(symbol);
         }
 
         // Set some properties on the last node so that it can be used as a representation of the
-        // incoming `word`.
+        // incoming sequence.
+        let current_node = &mut arena[current_node_index];
         current_node.node_type = TrieNodeType::Final;
+        current_node.word = Some(symbols);
         current_node.word_score = Some(score);
-
-        let mut node_to_mod = arena[current_node_index].write().unwrap();
-        node_to_mod.node_type = TrieNodeType::Final;
-        node_to_mod.word_score = Some(score);
+        current_node.payload = payload;
     }
 
-    fn _search(&self, word: &String) -> Option<OutputWrapper> {
-        // NOTE: We do not include the root of the trie when returning results, as it only contains
-        // an empty char, plus references to its children.
-        let mut node = &self.root;
-        let mut nodes: Vec<&TrieNode> = vec![];
-
-        let asdf = self.arena.read().unwrap();
-        let mut node_new = asdf.get(self.root_index).unwrap();
-        let mut node_idxs: Vec<usize> = vec![];
+    fn _search<I: IntoIterator<Item = K>>(&self, seq: I) -> Option<(Vec<K>, Option<V>)> {
+        let arena = self.arena.read().expect("RwLock poisoned");
+        let idx = Self::locate(&arena, seq)?;
+        let node = &arena[idx];
 
-        for char in word.chars() {
-            if let Some(next_node) = node.children.get(&char) {
-                nodes.push(next_node);
-                node = next_node;
-            } else {
-                return None;
-            }
-
-            if let Some(next_idx) = node_new.read().unwrap().children_new.get(&char) {
-                node_idxs.push(*next_idx);
-                node_new = asdf.get(*next_idx).unwrap();
+        match node.node_type {
+            TrieNodeType::Final => {
+                Some((node.word.clone().unwrap_or_default(), node.payload.clone()))
             }
+            TrieNodeType::Intermediate => None,
         }
-
-        let foo: String = node_idxs
-            .iter()
-            .map(|idx| asdf[*idx].read().unwrap().value.unwrap())
-            .collect();
-
-        println!("WHAT {}", foo);
-
-        Some(OutputWrapper { nodes })
     }
 
-    fn _get_ranked_results(&self, prefix: String, k: usize) -> Option<Vec<String>> {
+    fn _get_ranked_results<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+        k: usize,
+    ) -> Option<Vec<(Vec<K>, Option<V>)>> {
+        let arena = self.arena.read().expect("RwLock poisoned");
+        let start_idx = Self::locate(&arena, prefix)?;
+
         // Our collection of "found" items is represented by `OutputWrapper`
         // instances so that we can use a specific `Ord` trait implementation
-        // to order by the underlying word's score before returning.
-        let mut found_nodes: BinaryHeap<OutputWrapper> = BinaryHeap::new();
+        // to order by the underlying entry's score before returning.
+        let mut found_nodes: BinaryHeap<OutputWrapper<K, V>> = BinaryHeap::new();
         let mut max_word_score: i64 = 0;
 
-        let mut heap: BinaryHeap<QueueWrapper>;
-
-        if let Some(output_wrapper) = self._search(&prefix) {
-            heap = BinaryHeap::from(vec![output_wrapper.to_queue_wrapper()]);
-        } else {
-            return None;
-        }
+        let mut heap: BinaryHeap<QueueWrapper<K, V>> = BinaryHeap::from(vec![QueueWrapper {
+            node: &arena[start_idx],
+            arena: &arena,
+        }]);
 
         while let Some(queue_wrapper) = heap.pop() {
             if (k != 0 && queue_wrapper.output_score() < max_word_score) && found_nodes.len() >= k {
                 break;
             }
-            if let Some(TrieNodeType::Final) = queue_wrapper.leaf_type() {
+            if queue_wrapper.leaf_type() == TrieNodeType::Final {
                 found_nodes.push(queue_wrapper.to_output_wrapper());
                 max_word_score = cmp::max(max_word_score, queue_wrapper.output_score());
             }
-            if let Some(children) = queue_wrapper.children() {
-                for child in children {
-                    heap.push(queue_wrapper.new_with_node(child));
-                }
+            for child in queue_wrapper.children() {
+                heap.push(queue_wrapper.new_with_node(child));
             }
         }
 
         // NOTE: It's a bit convoluted to turn a `BinaryHeap` into a `Vec` with the values in heap
         // order. `BinaryHeap.into_iter_sorted` will do what we need, but it is not yet stable (see
         // https://github.com/rust-lang/rust/issues/59278).
-        let result: Vec<String> = found_nodes
+        let result: Vec<(Vec<K>, Option<V>)> = found_nodes
             .into_sorted_vec()
             .iter()
             .rev()
-            .map(|t| t.join())
+            .map(|t| (t.join(), t.payload().cloned()))
             .collect();
 
         Some(result)
     }
 
-    pub fn get_ranked_results(&self, prefix: String) -> Option<Vec<String>> {
+    pub fn get_ranked_results<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+    ) -> Option<Vec<(Vec<K>, Option<V>)>> {
         self._get_ranked_results(prefix, 0)
     }
 
-    pub fn get_k_ranked_results(&self, prefix: String, k: usize) -> Option<Vec<String>> {
+    pub fn get_k_ranked_results<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+        k: usize,
+    ) -> Option<Vec<(Vec<K>, Option<V>)>> {
         self._get_ranked_results(prefix, k)
     }
 
-    pub fn insert(&mut self, word: String) {
-        self._insert(word, 0);
+    /// Like `get_ranked_results`, but orders matches by an explicit, ordered list of `Criterion`s
+    /// applied lexicographically (earlier criteria dominate, later ones break ties) instead of by
+    /// `word_score` alone.
+    pub fn get_ranked_results_by<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+        criteria: &[Box<dyn Criterion<K>>],
+    ) -> Option<Vec<(Vec<K>, Option<V>)>> {
+        let arena = self.arena.read().expect("RwLock poisoned");
+        let start_idx = Self::locate(&arena, prefix)?;
+
+        let mut candidates: Vec<(Vec<K>, i64, Option<V>)> = vec![];
+        let mut stack = vec![start_idx];
+
+        while let Some(idx) = stack.pop() {
+            let node = &arena[idx];
+            if node.node_type == TrieNodeType::Final {
+                candidates.push((
+                    node.word.clone().unwrap_or_default(),
+                    node.word_score.unwrap_or_default(),
+                    node.payload.clone(),
+                ));
+            }
+            stack.extend(node.children.values().copied());
+        }
+
+        candidates.sort_by(|(word_a, score_a, _), (word_b, score_b, _)| {
+            let candidate_a = Candidate {
+                word: word_a,
+                word_score: *score_a,
+            };
+            let candidate_b = Candidate {
+                word: word_b,
+                word_score: *score_b,
+            };
+
+            criteria
+                .iter()
+                .map(|criterion| {
+                    criterion
+                        .rank(&candidate_b)
+                        .cmp(&criterion.rank(&candidate_a))
+                })
+                .find(|ordering| *ordering != cmp::Ordering::Equal)
+                .unwrap_or(cmp::Ordering::Equal)
+        });
+
+        Some(
+            candidates
+                .into_iter()
+                .map(|(word, _, payload)| (word, payload))
+                .collect(),
+        )
     }
 
-    pub fn insert_with_score(&mut self, word: String, score: i64) {
-        self._insert(word, score);
+    pub fn insert<I: IntoIterator<Item = K>>(&mut self, seq: I) {
+        self._insert(seq, 0, None);
     }
 
-    pub fn search(&self, word: String) -> Option<String> {
-        if let Some(output_wrapper) = self._search(&word) {
-            match output_wrapper.leaf_type() {
-                Some(TrieNodeType::Final) => Some(output_wrapper.join()),
-                _ => None,
+    pub fn insert_with_score<I: IntoIterator<Item = K>>(&mut self, seq: I, score: i64) {
+        self._insert(seq, score, None);
+    }
+
+    pub fn insert_with_value<I: IntoIterator<Item = K>>(&mut self, seq: I, score: i64, value: V) {
+        self._insert(seq, score, Some(value));
+    }
+
+    pub fn search<I: IntoIterator<Item = K>>(&self, seq: I) -> Option<(Vec<K>, Option<V>)> {
+        self._search(seq)
+    }
+
+    pub fn starts_with<I: IntoIterator<Item = K> + Clone>(&self, prefix: I) -> Option<Vec<K>> {
+        let arena = self.arena.read().expect("RwLock poisoned");
+        Self::locate(&arena, prefix.clone()).map(|_| prefix.into_iter().collect())
+    }
+
+    /// Returns the longest stored entry that is itself a prefix of `seq` (e.g. the longest
+    /// matching route in a URL router, or the longest token in a longest-match tokenizer).
+    pub fn find_longest_prefix<I: IntoIterator<Item = K>>(
+        &self,
+        seq: I,
+    ) -> Option<(Vec<K>, Option<V>)> {
+        let arena = self.arena.read().expect("RwLock poisoned");
+
+        let mut current_node_index = 0;
+        let mut longest: Option<usize> = None;
+
+        for symbol in seq {
+            match arena[current_node_index].children.get(&symbol) {
+                Some(&next_idx) => {
+                    current_node_index = next_idx;
+                    if arena[current_node_index].node_type == TrieNodeType::Final {
+                        longest = Some(current_node_index);
+                    }
+                }
+                None => break,
             }
-        } else {
-            return None;
         }
+
+        longest.map(|idx| {
+            let node = &arena[idx];
+            (node.word.clone().unwrap_or_default(), node.payload.clone())
+        })
     }
 
-    pub fn starts_with(&self, prefix: String) -> Option<String> {
-        match self._search(&prefix) {
-            Some(output_wrapper) => Some(output_wrapper.join()),
-            _ => None,
+    /// Returns every stored entry along the path of `seq` that is a prefix of `seq`, in
+    /// increasing length order.
+    pub fn find_prefixes<I: IntoIterator<Item = K>>(&self, seq: I) -> Vec<(Vec<K>, Option<V>)> {
+        let arena = self.arena.read().expect("RwLock poisoned");
+
+        let mut current_node_index = 0;
+        let mut results = vec![];
+
+        for symbol in seq {
+            match arena[current_node_index].children.get(&symbol) {
+                Some(&next_idx) => {
+                    current_node_index = next_idx;
+                    let node = &arena[current_node_index];
+                    if node.node_type == TrieNodeType::Final {
+                        results.push((node.word.clone().unwrap_or_default(), node.payload.clone()));
+                    }
+                }
+                None => break,
+            }
+        }
+
+        results
+    }
+
+    /// Returns every stored entry that extends `prefix`, i.e. the same subtree traversal used by
+    /// `get_ranked_results`, without the scoring heap.
+    pub fn find_postfixes<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+    ) -> Option<Vec<(Vec<K>, Option<V>)>> {
+        let arena = self.arena.read().expect("RwLock poisoned");
+        let start_idx = Self::locate(&arena, prefix)?;
+
+        let mut results = vec![];
+        let mut stack = vec![start_idx];
+
+        while let Some(idx) = stack.pop() {
+            let node = &arena[idx];
+            if node.node_type == TrieNodeType::Final {
+                results.push((node.word.clone().unwrap_or_default(), node.payload.clone()));
+            }
+            stack.extend(node.children.values().copied());
+        }
+
+        Some(results)
+    }
+
+    /// Returns every stored entry of the same length as `seq` that differs from it by exactly
+    /// one substituted symbol — a "magic dictionary" lookup, cheaper than full fuzzy search when
+    /// only single substitutions matter.
+    pub fn find_one_edit_matches<I: IntoIterator<Item = K>>(
+        &self,
+        seq: I,
+    ) -> Vec<(Vec<K>, Option<V>)> {
+        let word: Vec<K> = seq.into_iter().collect();
+        let arena = self.arena.read().expect("RwLock poisoned");
+
+        let mut found = vec![];
+        Self::collect_one_edit_matches(&arena, 0, &word, 0, false, &mut found);
+        found
+    }
+
+    // Walks the trie carrying an index into `word` and whether a substitution has already been
+    // used, descending into a child that either matches the next symbol in `word` (`used_edit`
+    // unchanged) or differs from it (only if `used_edit` wasn't already set) — pruning whenever a
+    // second mismatch would be needed. A `Final` node reached with the whole of `word` consumed
+    // and `used_edit` set is a match.
+    fn collect_one_edit_matches(
+        arena: &[TrieNode<K, V>],
+        node_idx: usize,
+        word: &[K],
+        i: usize,
+        used_edit: bool,
+        found: &mut Vec<(Vec<K>, Option<V>)>,
+    ) {
+        if i == word.len() {
+            let node = &arena[node_idx];
+            if used_edit && node.node_type == TrieNodeType::Final {
+                found.push((node.word.clone().unwrap_or_default(), node.payload.clone()));
+            }
+            return;
+        }
+
+        for (symbol, &child_idx) in arena[node_idx].children.iter() {
+            if *symbol == word[i] {
+                Self::collect_one_edit_matches(arena, child_idx, word, i + 1, used_edit, found);
+            } else if !used_edit {
+                Self::collect_one_edit_matches(arena, child_idx, word, i + 1, true, found);
+            }
         }
     }
+
+    // Walks the trie carrying `row`, this node's row of the Levenshtein DP table: `row.last()` is
+    // `edit_distance(query, path_to_this_node)`. This is "prefix mode" (autocomplete), so once
+    // that distance is `<= max_edits` at some depth, every `Final` node further down that same
+    // branch is still a match regardless of how much the full-length distance grows as the word
+    // continues past `query` — `prefix_matched` carries that "already matched at this depth or
+    // shallower" state down the recursion. Once set, there's no more DP work to do: the rest of
+    // the subtree is collected the same way `find_postfixes` walks one.
+    fn collect_fuzzy_matches<'a>(
+        arena: &'a [TrieNode<K, V>],
+        node_idx: usize,
+        query: &[K],
+        row: &[usize],
+        max_edits: u8,
+        prefix_matched: bool,
+        found: &mut BinaryHeap<OutputWrapper<'a, K, V>>,
+    ) {
+        let node = &arena[node_idx];
+        let prefix_matched = prefix_matched || *row.last().unwrap() <= max_edits as usize;
+
+        if node.node_type == TrieNodeType::Final && prefix_matched {
+            found.push(OutputWrapper { node });
+        }
+
+        if prefix_matched {
+            for &child_idx in node.children.values() {
+                Self::collect_fuzzy_matches(arena, child_idx, query, row, max_edits, true, found);
+            }
+            return;
+        }
+
+        for (symbol, &child_idx) in node.children.iter() {
+            let mut child_row = vec![row[0] + 1];
+            for i in 1..=query.len() {
+                let substitution_cost = usize::from(query[i - 1] != *symbol);
+                child_row.push(
+                    (child_row[i - 1] + 1) // insertion
+                        .min(row[i] + 1) // deletion
+                        .min(row[i - 1] + substitution_cost), // substitution
+                );
+            }
+
+            if *child_row.iter().min().unwrap() > max_edits as usize {
+                continue;
+            }
+
+            Self::collect_fuzzy_matches(
+                arena, child_idx, query, &child_row, max_edits, false, found,
+            );
+        }
+    }
+}
+
+impl<K, V> Default for Trie<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Thin specialization of `Trie<K, V>` for the common case of `char`-keyed,
+/// `String`-valued words, matching the API the crate originally shipped.
+impl<V> Trie<char, V>
+where
+    V: Clone,
+{
+    pub fn insert_word(&mut self, word: String) {
+        self.insert(word.chars());
+    }
+
+    pub fn insert_word_with_score(&mut self, word: String, score: i64) {
+        self.insert_with_score(word.chars(), score);
+    }
+
+    pub fn search_word(&self, word: String) -> Option<String> {
+        self.search(word.chars())
+            .map(|(chars, _)| chars.into_iter().collect())
+    }
+
+    pub fn starts_with_word(&self, prefix: String) -> Option<String> {
+        self.starts_with(prefix.chars())
+            .map(|chars| chars.into_iter().collect())
+    }
+
+    pub fn get_ranked_words(&self, prefix: String) -> Option<Vec<String>> {
+        self.get_ranked_results(prefix.chars()).map(|results| {
+            results
+                .into_iter()
+                .map(|(chars, _)| chars.into_iter().collect())
+                .collect()
+        })
+    }
+
+    pub fn get_k_ranked_words(&self, prefix: String, k: usize) -> Option<Vec<String>> {
+        self.get_k_ranked_results(prefix.chars(), k).map(|results| {
+            results
+                .into_iter()
+                .map(|(chars, _)| chars.into_iter().collect())
+                .collect()
+        })
+    }
+
+    pub fn get_ranked_words_by(
+        &self,
+        prefix: String,
+        criteria: &[Box<dyn Criterion<char>>],
+    ) -> Option<Vec<String>> {
+        self.get_ranked_results_by(prefix.chars(), criteria)
+            .map(|results| {
+                results
+                    .into_iter()
+                    .map(|(chars, _)| chars.into_iter().collect())
+                    .collect()
+            })
+    }
+
+    pub fn find_longest_prefix_word(&self, word: String) -> Option<String> {
+        self.find_longest_prefix(word.chars())
+            .map(|(chars, _)| chars.into_iter().collect())
+    }
+
+    pub fn find_prefix_words(&self, word: String) -> Vec<String> {
+        self.find_prefixes(word.chars())
+            .into_iter()
+            .map(|(chars, _)| chars.into_iter().collect())
+            .collect()
+    }
+
+    pub fn find_postfix_words(&self, prefix: String) -> Option<Vec<String>> {
+        self.find_postfixes(prefix.chars()).map(|results| {
+            results
+                .into_iter()
+                .map(|(chars, _)| chars.into_iter().collect())
+                .collect()
+        })
+    }
+
+    /// Typo-tolerant prefix search: returns every stored word whose prefix is within
+    /// `max_edits` edits of `query`, ranked using the same scoring as `get_ranked_results`. This
+    /// gives autocomplete that tolerates user typos, e.g. `query` `"Forex"` with `max_edits: 1`
+    /// matches a longer stored word like `"Foreign"`, not just same-length near-matches.
+    pub fn get_fuzzy_results(&self, query: String, max_edits: u8) -> Vec<String> {
+        let query_chars: Vec<char> = query.chars().collect();
+        let initial_row: Vec<usize> = (0..=query_chars.len()).collect();
+
+        let arena = self.arena.read().expect("RwLock poisoned");
+        let mut found_nodes: BinaryHeap<OutputWrapper<char, V>> = BinaryHeap::new();
+        Self::collect_fuzzy_matches(
+            &arena,
+            0,
+            &query_chars,
+            &initial_row,
+            max_edits,
+            false,
+            &mut found_nodes,
+        );
+
+        found_nodes
+            .into_sorted_vec()
+            .iter()
+            .rev()
+            .map(|wrapper| wrapper.join().into_iter().collect())
+            .collect()
+    }
+
+    /// Returns whether any stored word differs from `word` by exactly one substituted
+    /// character (same length, one position changed) — a common spell-suggestion /
+    /// "did you mean" dictionary feature.
+    pub fn search_one_edit(&self, word: String) -> bool {
+        !self.find_one_edit_matches(word.chars()).is_empty()
+    }
+
+    /// Like `search_one_edit`, but returns every stored word that differs from `word` by exactly
+    /// one substituted character, instead of just whether one exists.
+    pub fn get_one_edit_words(&self, word: String) -> Vec<String> {
+        self.find_one_edit_matches(word.chars())
+            .into_iter()
+            .map(|(chars, _)| chars.into_iter().collect())
+            .collect()
+    }
+
+    /// Builds an online streaming matcher over this trie's current contents (see
+    /// `StreamMatcher`). The arena is snapshotted at construction time, so inserts made into
+    /// this trie afterwards are not reflected in the returned matcher.
+    pub fn stream_matcher(&self) -> StreamMatcher<V> {
+        StreamMatcher::new(self)
+    }
 }
 
 #[cfg(test)]
@@ -189,24 +558,24 @@ mod tests {
     #[test]
     fn can_search_for_term() {
         let search_term = "Foo";
-        let mut trie = Trie::new();
-        trie.insert(search_term.to_string());
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert(search_term.chars());
 
         assert_eq!(
-            Some(search_term.to_string()),
-            trie.search(search_term.to_string())
+            Some((search_term.chars().collect(), None)),
+            trie.search(search_term.chars())
         );
     }
 
     #[test]
     fn can_search_for_term_with_score() {
         let search_term = "Foo";
-        let mut trie = Trie::new();
-        trie.insert_with_score(search_term.to_string(), 10);
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_with_score(search_term.chars(), 10);
 
         assert_eq!(
-            Some(search_term.to_string()),
-            trie.search(search_term.to_string())
+            Some((search_term.chars().collect(), None)),
+            trie.search(search_term.chars())
         );
     }
 
@@ -215,28 +584,28 @@ mod tests {
         let search_term = "Foo";
         let insert_terms = vec!["Foo", "For"];
 
-        let mut trie = Trie::new();
+        let mut trie: Trie<char, ()> = Trie::new();
         for term in insert_terms {
-            trie.insert(term.to_string());
+            trie.insert(term.chars());
         }
 
         assert_eq!(
-            Some(search_term.to_string()),
-            trie.search(search_term.to_string())
+            Some((search_term.chars().collect(), None)),
+            trie.search(search_term.chars())
         );
     }
 
     #[test]
     fn can_find_starts_with_items() {
         let insert_term = "Foo";
-        let mut trie = Trie::new();
-        trie.insert(insert_term.to_string());
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert(insert_term.chars());
 
         let prefix = "Fo";
 
         assert_eq!(
-            Some(prefix.to_string()),
-            trie.starts_with(prefix.to_string())
+            Some(prefix.chars().collect::<Vec<char>>()),
+            trie.starts_with(prefix.chars())
         );
     }
 
@@ -245,10 +614,10 @@ mod tests {
         let insert_term = "Foo";
         let search_term = "Bar";
 
-        let mut trie = Trie::new();
-        trie.insert(insert_term.to_string());
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert(insert_term.chars());
 
-        assert_eq!(None, trie.search(search_term.to_string()));
+        assert_eq!(None, trie.search(search_term.chars()));
     }
 
     #[test]
@@ -256,35 +625,39 @@ mod tests {
         let insert_term = "Foo";
         let prefix = "Ba";
 
-        let mut trie = Trie::new();
-        trie.insert(insert_term.to_string());
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert(insert_term.chars());
 
-        assert_eq!(None, trie.starts_with(prefix.to_string()));
+        assert_eq!(None, trie.starts_with(prefix.chars()));
     }
 
     #[test]
     fn get_ranked_results_uses_score_ordering() {
-        let words_and_scores = vec![("Foreign", 10), ("For", 8), ("Foo", 0)];
+        let words_and_scores = [("Foreign", 10), ("For", 8), ("Foo", 0)];
 
         let expected_words: Vec<String> = words_and_scores
             .iter()
             .map(|(word, _)| word.to_string())
             .collect();
 
-        let mut trie = Trie::new();
+        let mut trie: Trie<char, ()> = Trie::new();
 
         for (word, score) in words_and_scores.iter() {
-            trie.insert_with_score(word.to_string(), *score);
+            trie.insert_with_score(word.chars(), *score);
         }
 
-        let ranked_results = trie.get_ranked_results("Fo".to_string()).unwrap();
+        let ranked_results = trie.get_ranked_results("Fo".chars()).unwrap();
+        let ranked_words: Vec<String> = ranked_results
+            .into_iter()
+            .map(|(chars, _)| chars.into_iter().collect())
+            .collect();
 
-        assert_eq!(expected_words, ranked_results);
+        assert_eq!(expected_words, ranked_words);
     }
 
     #[test]
     fn get_k_ranked_results_returns_correct_count() {
-        let words_and_scores = vec![("Foreign", 10), ("For", 8), ("Foo", 0)];
+        let words_and_scores = [("Foreign", 10), ("For", 8), ("Foo", 0)];
 
         // TODO: This seems like a silly way to construct this.
         let expected_words: Vec<String> = words_and_scores
@@ -293,14 +666,246 @@ mod tests {
             .collect::<Vec<String>>()[..2]
             .to_vec();
 
-        let mut trie = Trie::new();
+        let mut trie: Trie<char, ()> = Trie::new();
 
         for (word, score) in words_and_scores.iter() {
-            trie.insert_with_score(word.to_string(), *score);
+            trie.insert_with_score(word.chars(), *score);
         }
 
-        let ranked_results = trie.get_k_ranked_results("Fo".to_string(), 2).unwrap();
+        let ranked_results = trie.get_k_ranked_results("Fo".chars(), 2).unwrap();
+        let ranked_words: Vec<String> = ranked_results
+            .into_iter()
+            .map(|(chars, _)| chars.into_iter().collect())
+            .collect();
+
+        assert_eq!(expected_words[..2], ranked_words);
+    }
+
+    #[test]
+    fn can_attach_and_retrieve_a_payload() {
+        let mut trie: Trie<char, i32> = Trie::new();
+        trie.insert_with_value("Foo".chars(), 0, 42);
+
+        assert_eq!(
+            Some(("Foo".chars().collect(), Some(42))),
+            trie.search("Foo".chars())
+        );
+    }
+
+    #[test]
+    fn works_over_non_char_symbol_types() {
+        let mut trie: Trie<u8, ()> = Trie::new();
+        trie.insert("abc".bytes());
+
+        assert_eq!(
+            Some(("abc".bytes().collect(), None)),
+            trie.search("abc".bytes())
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_finds_exact_match_with_zero_edits() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Foo".to_string());
+
+        assert_eq!(
+            vec!["Foo".to_string()],
+            trie.get_fuzzy_results("Foo".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_tolerates_a_single_substitution() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Foo".to_string());
+
+        assert_eq!(
+            vec!["Foo".to_string()],
+            trie.get_fuzzy_results("Fon".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_excludes_matches_past_max_edits() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Foo".to_string());
+
+        let results = trie.get_fuzzy_results("Fon".to_string(), 0);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn get_fuzzy_results_ranks_by_score() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word_with_score("Foo".to_string(), 0);
+        trie.insert_word_with_score("Fon".to_string(), 10);
+
+        assert_eq!(
+            vec!["Fon".to_string(), "Foo".to_string()],
+            trie.get_fuzzy_results("Fon".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_matches_words_longer_than_the_query() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Foreign".to_string());
+        trie.insert_word("For".to_string());
+        trie.insert_word("Foo".to_string());
+
+        let mut results = trie.get_fuzzy_results("Fo".to_string(), 0);
+        results.sort();
+
+        assert_eq!(
+            vec!["Foo".to_string(), "For".to_string(), "Foreign".to_string()],
+            results
+        );
+    }
+
+    #[test]
+    fn get_fuzzy_results_tolerates_a_typo_in_a_prefix_of_a_longer_word() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Foreign".to_string());
+        trie.insert_word("Bar".to_string());
+
+        assert_eq!(
+            vec!["Foreign".to_string()],
+            trie.get_fuzzy_results("Forex".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn find_longest_prefix_returns_the_longest_stored_match() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Fo".to_string());
+        trie.insert_word("For".to_string());
+
+        assert_eq!(
+            Some("For".to_string()),
+            trie.find_longest_prefix_word("Foreign".to_string())
+        );
+    }
+
+    #[test]
+    fn find_longest_prefix_returns_none_without_a_stored_match() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Bar".to_string());
+
+        assert_eq!(None, trie.find_longest_prefix_word("Foreign".to_string()));
+    }
+
+    #[test]
+    fn find_prefixes_returns_all_stored_matches_in_increasing_length_order() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Fo".to_string());
+        trie.insert_word("For".to_string());
+        trie.insert_word("Fort".to_string());
+
+        assert_eq!(
+            vec!["Fo".to_string(), "For".to_string(), "Fort".to_string()],
+            trie.find_prefix_words("Fort".to_string())
+        );
+    }
+
+    #[test]
+    fn find_postfixes_returns_all_words_extending_a_prefix() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Foo".to_string());
+        trie.insert_word("For".to_string());
+        trie.insert_word("Bar".to_string());
+
+        let mut postfixes = trie.find_postfix_words("Fo".to_string()).unwrap();
+        postfixes.sort();
+
+        assert_eq!(vec!["Foo".to_string(), "For".to_string()], postfixes);
+    }
+
+    #[test]
+    fn find_postfixes_returns_none_for_a_missing_prefix() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("Foo".to_string());
+
+        assert_eq!(None, trie.find_postfix_words("Ba".to_string()));
+    }
+
+    #[test]
+    fn get_ranked_results_by_applies_criteria_lexicographically() {
+        use crate::criteria::ShorterWordFirst;
+
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word_with_score("Foreign".to_string(), 10);
+        trie.insert_word_with_score("For".to_string(), 0);
+
+        let criteria: Vec<Box<dyn crate::criteria::Criterion<char>>> =
+            vec![Box::new(ShorterWordFirst)];
+
+        assert_eq!(
+            vec!["For".to_string(), "Foreign".to_string()],
+            trie.get_ranked_words_by("Fo".to_string(), &criteria)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn get_ranked_results_by_breaks_ties_with_later_criteria() {
+        use crate::criteria::{ShorterWordFirst, WordScore};
+
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word_with_score("For".to_string(), 0);
+        trie.insert_word_with_score("Fox".to_string(), 10);
+
+        let criteria: Vec<Box<dyn crate::criteria::Criterion<char>>> =
+            vec![Box::new(ShorterWordFirst), Box::new(WordScore)];
+
+        assert_eq!(
+            vec!["Fox".to_string(), "For".to_string()],
+            trie.get_ranked_words_by("Fo".to_string(), &criteria)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn search_one_edit_finds_a_single_substitution() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("hit".to_string());
+
+        assert!(trie.search_one_edit("hot".to_string()));
+    }
+
+    #[test]
+    fn search_one_edit_rejects_an_exact_match() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("hit".to_string());
+
+        assert!(!trie.search_one_edit("hit".to_string()));
+    }
+
+    #[test]
+    fn search_one_edit_rejects_a_different_length() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("hit".to_string());
+
+        assert!(!trie.search_one_edit("hits".to_string()));
+    }
+
+    #[test]
+    fn search_one_edit_rejects_two_substitutions() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("hit".to_string());
+
+        assert!(!trie.search_one_edit("hop".to_string()));
+    }
+
+    #[test]
+    fn get_one_edit_words_returns_every_single_substitution_match() {
+        let mut trie: Trie<char, ()> = Trie::new();
+        trie.insert_word("hit".to_string());
+        trie.insert_word("hot".to_string());
+        trie.insert_word("hat".to_string());
+
+        let mut matches = trie.get_one_edit_words("hit".to_string());
+        matches.sort();
 
-        assert_eq!(expected_words[..2], ranked_results);
+        assert_eq!(vec!["hat".to_string(), "hot".to_string()], matches);
     }
 }
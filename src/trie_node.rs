@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::hash::Hash;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum TrieNodeType {
@@ -7,36 +8,74 @@ pub enum TrieNodeType {
     Intermediate,
 }
 
-#[derive(Clone, Eq, PartialEq)]
-pub struct TrieNode {
-    pub value: Option<char>,
-    pub children: HashMap<char, TrieNode>,
+/// A single arena-indexed trie node. `children` maps an edge symbol to the index of the child
+/// node within the owning `Trie`'s arena, rather than embedding the child inline, so that the
+/// trie can be represented as one flat `Vec<TrieNode<K, V>>` instead of a tree of owned nodes.
+#[derive(Clone)]
+pub struct TrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    pub children: HashMap<K, usize>,
     pub node_type: TrieNodeType,
+    // The full key sequence this node represents, populated once the node becomes `Final`.
+    pub word: Option<Vec<K>>,
     pub word_score: Option<i64>,
     pub node_score: i64,
-    pub children_new: HashMap<char, usize>,
+    pub payload: Option<V>,
 }
 
-impl TrieNode {
-    pub fn new(value: Option<char>) -> Self {
+impl<K, V> TrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
         TrieNode {
-            value,
             children: HashMap::new(),
             node_type: TrieNodeType::Intermediate,
+            word: None,
             word_score: None,
             node_score: 0,
-            children_new: HashMap::new(),
+            payload: None,
         }
     }
 }
 
-impl Ord for TrieNode {
+impl<K, V> Default for TrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// `Eq`/`Ord` are implemented in terms of `node_score` alone, so the payload type `V` never needs
+// to be comparable.
+impl<K, V> PartialEq for TrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.node_score == other.node_score
+    }
+}
+
+impl<K, V> Eq for TrieNode<K, V> where K: Eq + Hash + Clone {}
+
+impl<K, V> Ord for TrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn cmp(&self, other: &Self) -> Ordering {
         self.node_score.cmp(&other.node_score)
     }
 }
 
-impl PartialOrd for TrieNode {
+impl<K, V> PartialOrd for TrieNode<K, V>
+where
+    K: Eq + Hash + Clone,
+{
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }